@@ -2,6 +2,7 @@ use colored::Colorize;
 use reqwest::blocking::{Client, Response};
 use serde_json::json;
 use spinners::Spinner;
+use std::io::{BufRead, BufReader, Write};
 
 use crate::prompts;
 use crate::{Cli, Config, Mode};
@@ -18,6 +19,8 @@ impl ChatCompletions {
         let mode = match cli.mode.as_str() {
             "commit" => Mode::Commit,
             "command" => Mode::Command,
+            "undo" => Mode::Undo,
+            "release" => Mode::Release,
             _ => Mode::Command,
         };
         Self {
@@ -32,10 +35,16 @@ impl ChatCompletions {
         self.system_prompt = system_prompt.prompt(&self.cli);
     }
 
+    pub fn refine_loop(self: &Self, prompt: prompts::Prompt, spinner: &mut Spinner) -> String {
+        // `run` stops the spinner itself once the first streamed token arrives.
+        return self.run(prompt.content, spinner);
+    }
+
     pub fn run(self: &Self, prompt: String, spinner: &mut Spinner) -> String {
         let client = Client::new();
         let api_addr = format!("{}/chat/completions", self.config.api_base);
         let max_tokens = self.cli.token_limit.unwrap_or(self.config.max_tokens);
+        let model = self.cli.model.clone().unwrap_or_else(|| self.config.model.clone());
 
         let response = client
             .post(api_addr)
@@ -45,7 +54,8 @@ impl ChatCompletions {
                 "max_tokens": max_tokens,
                 "presence_penalty": 0,
                 "frequency_penalty": 0,
-                "model": "gpt-3.5-turbo",
+                "model": model,
+                "stream": true,
                 "messages": [
                     {"role": "system", "content": self.system_prompt},
                     {"role": "user", "content": prompt}
@@ -56,14 +66,47 @@ impl ChatCompletions {
             .unwrap();
 
         let validated_response = self.validate_response(response, spinner);
-        let response_string = validated_response.json::<serde_json::Value>().unwrap()["choices"][0]
-            ["message"]["content"]
-            .as_str()
-            .unwrap()
-            .trim()
-            .to_string();
+        return self.stream_response(validated_response, spinner);
+    }
+
+    // Reads a `text/event-stream` response line by line, printing each token as it
+    // arrives and stopping the spinner on the first one, then returns the assembled string.
+    fn stream_response(self: &Self, response: Response, spinner: &mut Spinner) -> String {
+        let mut content = String::new();
+        let mut spinner_stopped = false;
+
+        for line in BufReader::new(response).lines() {
+            let line = line.unwrap_or_default();
+            let Some(data) = line.strip_prefix("data: ") else {
+                continue;
+            };
+            if data == "[DONE]" {
+                break;
+            }
+
+            let chunk: serde_json::Value = match serde_json::from_str(data) {
+                Ok(chunk) => chunk,
+                Err(_) => continue,
+            };
+            let Some(delta) = chunk["choices"][0]["delta"]["content"].as_str() else {
+                continue;
+            };
+
+            if !spinner_stopped {
+                spinner.stop();
+                spinner_stopped = true;
+            }
+            print!("{delta}");
+            std::io::stdout().flush().unwrap();
+            content.push_str(delta);
+        }
+
+        if !spinner_stopped {
+            spinner.stop();
+        }
+        println!();
 
-        return response_string;
+        return content.trim().to_string();
     }
 
     fn validate_response(self: &Self, response: Response, spinner: &mut Spinner) -> Response {
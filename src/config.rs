@@ -0,0 +1,108 @@
+use crate::Cli;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use spinners::{Spinner, Spinners};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Config {
+    #[serde(default)]
+    pub api_key: String,
+
+    #[serde(default = "default_api_base")]
+    pub api_base: String,
+
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: usize,
+
+    #[serde(default = "default_model")]
+    pub model: String,
+
+    #[serde(default)]
+    pub key_command: Option<String>,
+}
+
+fn default_api_base() -> String {
+    return "https://api.openai.com/v1".to_string();
+}
+
+fn default_max_tokens() -> usize {
+    return 1000;
+}
+
+fn default_model() -> String {
+    return "gpt-3.5-turbo".to_string();
+}
+
+impl Config {
+    pub fn new(cli: &Cli) -> Self {
+        let path = Self::path();
+        let contents = fs::read_to_string(&path).unwrap_or_else(|_| {
+            println!("Couldn't find a config file at {}", path.display());
+            std::process::exit(1);
+        });
+
+        let mut config: Config = serde_json::from_str(&contents).unwrap_or_else(|_| {
+            println!("Couldn't parse the config file at {}", path.display());
+            std::process::exit(1);
+        });
+
+        if let Some(key_command) = cli.key_command.clone().or_else(|| config.key_command.clone()) {
+            config.api_key = Self::run_key_command(&key_command);
+        }
+
+        return config;
+    }
+
+    // Runs `command` and returns its trimmed stdout.
+    fn run_key_command(command: &str) -> String {
+        let mut spinner = Spinner::new(Spinners::BouncingBar, "Fetching the API key...".into());
+
+        let argv = shlex::split(command).unwrap_or_default();
+        let Some((program, args)) = argv.split_first() else {
+            spinner.stop_and_persist(
+                "✖".red().to_string().as_str(),
+                "key_command must not be empty.".red().to_string(),
+            );
+            std::process::exit(1);
+        };
+
+        let output = Command::new(program).args(args).output().unwrap_or_else(|_| {
+            spinner.stop_and_persist(
+                "✖".red().to_string().as_str(),
+                format!("Failed to execute key_command \"{command}\".").red().to_string(),
+            );
+            std::process::exit(1);
+        });
+
+        if !output.status.success() {
+            spinner.stop_and_persist(
+                "✖".red().to_string().as_str(),
+                format!("key_command \"{command}\" exited with a non-zero status.").red().to_string(),
+            );
+            std::process::exit(1);
+        }
+
+        let key = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if key.is_empty() {
+            spinner.stop_and_persist(
+                "✖".red().to_string().as_str(),
+                format!("key_command \"{command}\" returned no output.").red().to_string(),
+            );
+            std::process::exit(1);
+        }
+
+        spinner.stop_and_persist(
+            "✔".green().to_string().as_str(),
+            "Fetched the API key.".green().to_string(),
+        );
+        return key;
+    }
+
+    fn path() -> PathBuf {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        return PathBuf::from(home).join(".config").join("plz").join("config.json");
+    }
+}
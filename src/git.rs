@@ -23,3 +23,80 @@ pub fn get_commit_changes() -> Option<Vec<String>> {
         .collect::<Vec<String>>();
     return Some(diff);
 }
+
+pub fn get_status() -> Option<String> {
+    // Get the working tree status in porcelain format
+    let status = Command::new("git")
+        .arg("status")
+        .arg("--porcelain")
+        .output()
+        .unwrap_or_else(|_| {
+            println!("Failed to execute git status.");
+            std::process::exit(1);
+        });
+
+    let status = String::from_utf8_lossy(&status.stdout).to_string();
+    if status.is_empty() {
+        return None;
+    }
+    return Some(status);
+}
+
+pub fn get_latest_tag() -> Option<String> {
+    // Get the most recent reachable tag
+    let tag = Command::new("git")
+        .arg("describe")
+        .arg("--tags")
+        .arg("--abbrev=0")
+        .output()
+        .unwrap_or_else(|_| {
+            println!("Failed to execute git describe.");
+            std::process::exit(1);
+        });
+
+    let tag = String::from_utf8_lossy(&tag.stdout).trim().to_string();
+    if tag.is_empty() {
+        return None;
+    }
+    return Some(tag);
+}
+
+pub fn get_log_range(from: &str, to: &str) -> Option<String> {
+    // Get the subject and body of every commit in the (from, to] range
+    let range = format!("{from}..{to}");
+    let log = Command::new("git")
+        .arg("log")
+        .arg("--pretty=format:%s%n%b%n--END--")
+        .arg(range)
+        .output()
+        .unwrap_or_else(|_| {
+            println!("Failed to execute git log.");
+            std::process::exit(1);
+        });
+
+    let log = String::from_utf8_lossy(&log.stdout).to_string();
+    if log.is_empty() {
+        return None;
+    }
+    return Some(log);
+}
+
+pub fn get_log(n: usize) -> Option<String> {
+    // Get the last n commits as one-line summaries
+    let log = Command::new("git")
+        .arg("log")
+        .arg("--oneline")
+        .arg("-n")
+        .arg(n.to_string())
+        .output()
+        .unwrap_or_else(|_| {
+            println!("Failed to execute git log.");
+            std::process::exit(1);
+        });
+
+    let log = String::from_utf8_lossy(&log.stdout).to_string();
+    if log.is_empty() {
+        return None;
+    }
+    return Some(log);
+}
@@ -0,0 +1,105 @@
+use regex::Regex;
+
+/// Default maximum length for a conventional-commit description.
+pub const MAX_DESCRIPTION_LENGTH: usize = 72;
+
+const ALLOWED_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+/// A single conventional-commit rule that the header or body failed to satisfy.
+pub struct Violation {
+    pub rule: &'static str,
+    pub message: String,
+}
+
+/// Lint a commit message against the conventional-commit rule set, returning
+/// every rule it violates. An empty result means the message is valid.
+pub fn lint_commit_message(message: &str, max_description_length: usize) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut lines = message.lines();
+    let header = lines.next().unwrap_or("");
+
+    match parse_header(header) {
+        Some((commit_type, scope, description)) => {
+            if commit_type.is_empty() {
+                violations.push(Violation {
+                    rule: "type",
+                    message: "commit type must not be empty".to_string(),
+                });
+            } else if commit_type != commit_type.to_lowercase() {
+                violations.push(Violation {
+                    rule: "type",
+                    message: format!("commit type \"{commit_type}\" must be lowercase"),
+                });
+            } else if !ALLOWED_TYPES.contains(&commit_type.as_str()) {
+                violations.push(Violation {
+                    rule: "type",
+                    message: format!(
+                        "commit type \"{commit_type}\" is not one of: {}",
+                        ALLOWED_TYPES.join(", ")
+                    ),
+                });
+            }
+
+            if let Some(scope) = &scope {
+                if scope.contains(' ') {
+                    violations.push(Violation {
+                        rule: "scope",
+                        message: format!("scope \"{scope}\" must not contain spaces"),
+                    });
+                }
+            }
+
+            if description.trim().is_empty() {
+                violations.push(Violation {
+                    rule: "description",
+                    message: "description must not be empty".to_string(),
+                });
+            } else {
+                let description_length = description.chars().count();
+                if description_length > max_description_length {
+                    violations.push(Violation {
+                        rule: "description",
+                        message: format!(
+                            "description is {description_length} characters, must be at most {max_description_length}"
+                        ),
+                    });
+                }
+                if description.ends_with('.') {
+                    violations.push(Violation {
+                        rule: "description",
+                        message: "description must not end in a period".to_string(),
+                    });
+                }
+            }
+        }
+        None => {
+            violations.push(Violation {
+                rule: "header",
+                message: "header does not match `<type>(<optional scope>)!?: <description>`"
+                    .to_string(),
+            });
+        }
+    }
+
+    if let Some(second_line) = lines.next() {
+        if !second_line.is_empty() {
+            violations.push(Violation {
+                rule: "body",
+                message: "there must be a blank line between the header and the body".to_string(),
+            });
+        }
+    }
+
+    return violations;
+}
+
+fn parse_header(header: &str) -> Option<(String, Option<String>, String)> {
+    let re = Regex::new(r"^([a-zA-Z]+)(\(([^)]*)\))?!?: (.*)$").unwrap();
+    let captures = re.captures(header)?;
+    let commit_type = captures.get(1)?.as_str().to_string();
+    let scope = captures.get(3).map(|m| m.as_str().to_string());
+    let description = captures.get(4)?.as_str().to_string();
+    return Some((commit_type, scope, description));
+}
@@ -2,18 +2,21 @@
 
 use bat::PrettyPrinter;
 use chat_gpt::completions::ChatCompletions;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::Shell;
 use colored::Colorize;
 use config::Config;
 use question::{Answer, Question};
 use reqwest::blocking::Response;
 use spinners::{Spinner, Spinners};
+use std::fs;
 use std::process::Command;
 
 mod chat_gpt;
 mod config;
 mod git;
 mod gitmoji;
+mod lint;
 mod prompts;
 
 use crate::chat_gpt::completions;
@@ -40,11 +43,85 @@ pub struct Cli {
 
     #[clap(short = 'H', long)]
     hint: Option<String>,
+
+    /// Maximum length of a conventional-commit description
+    #[clap(long, default_value_t = lint::MAX_DESCRIPTION_LENGTH)]
+    max_description_length: usize,
+
+    /// Abort instead of committing when the message still fails conventional-commit linting
+    #[clap(long)]
+    strict: bool,
+
+    /// Print what would be run without executing it
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Ref to start the release notes from (defaults to the latest tag)
+    #[clap(long)]
+    from: Option<String>,
+
+    /// Ref to end the release notes at
+    #[clap(long, default_value = "HEAD")]
+    to: String,
+
+    /// Model to use for the completion, e.g. gpt-4 (defaults to the model set in the config file)
+    #[clap(long)]
+    model: Option<String>,
+
+    /// Print a shell completion script for the given shell and exit
+    #[clap(long, value_enum)]
+    completions: Option<Shell>,
+
+    /// Command to run to fetch the API key (e.g. `pass show openai`), instead of reading it from the config file
+    #[clap(long)]
+    key_command: Option<String>,
+}
+
+/// Shell metacharacters that can't be represented as a plain argv and require `bash -c`.
+const SHELL_METACHARACTERS: &[&str] = &[
+    "|", "&", ">", "<", ";", "$", "~", "*", "?", "{", "}", "`",
+];
+
+fn has_shell_metacharacters(command: &str) -> bool {
+    return SHELL_METACHARACTERS.iter().any(|m| command.contains(m));
+}
+
+/// Wraps `value` in single quotes, escaping any single quotes it contains so it
+/// always round-trips through both a POSIX shell and `shlex`.
+fn shell_single_quote(value: &str) -> String {
+    return format!("'{}'", value.replace('\'', "'\\''"));
+}
+
+fn explain_cmd(command: &str) {
+    if has_shell_metacharacters(command) {
+        println!(
+            "{}",
+            "Contains shell metacharacters, will run through the shell:".yellow()
+        );
+        pprint(&command.to_string(), "bash");
+        return;
+    }
+
+    match shlex::split(command) {
+        Some(argv) => {
+            println!("{}", "Will run directly, without a shell:".yellow());
+            pprint(&format!("{argv:?}"), "bash");
+        }
+        None => {
+            println!(
+                "{}",
+                "Couldn't tokenize the command, will run through the shell:".yellow()
+            );
+            pprint(&command.to_string(), "bash");
+        }
+    }
 }
 
 pub enum Mode {
     Command,
     Commit,
+    Undo,
+    Release,
 }
 
 impl std::fmt::Display for Mode {
@@ -52,18 +129,27 @@ impl std::fmt::Display for Mode {
         match self {
             Mode::Command => write!(f, "command"),
             Mode::Commit => write!(f, "commit"),
+            Mode::Undo => write!(f, "undo"),
+            Mode::Release => write!(f, "release"),
         }
     }
 }
 
 fn main() {
+    let cli = Cli::parse();
+
+    // Must not require an API key or a git repo, so handle it before any setup.
+    if let Some(shell) = cli.completions {
+        generate_completions(shell);
+        return;
+    }
+
     println!(
         "{} {}",
         "🤖".bright_green(),
         "Welcome to deez AI!".bright_green()
     );
-    let cli = Cli::parse();
-    let config = Config::new();
+    let config = Config::new(&cli);
     let chat_completions = completions::ChatCompletions::new(cli, config);
     println!("AI mode: {}", chat_completions.mode);
 
@@ -74,14 +160,32 @@ fn main() {
         Mode::Commit => {
             commit_workflow(chat_completions);
         }
+        Mode::Undo => {
+            undo_workflow(chat_completions);
+        }
+        Mode::Release => {
+            release_workflow(chat_completions);
+        }
     }
 }
 
+fn generate_completions(shell: Shell) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+}
+
 fn command_run_workflow(mut chat_completions: completions::ChatCompletions) {
     chat_completions.set_system_prompt(prompts::SystemPrompt::Cmd);
     let mut spinner = Spinner::new(Spinners::BouncingBar, "Generating your command...".into());
     let user_prompt = prompts::get_cmd_user_prompt(&chat_completions.cli.prompt.join(" "));
     let code = chat_completions.refine_loop(user_prompt, &mut spinner);
+
+    if chat_completions.cli.dry_run {
+        explain_cmd(&code);
+        return;
+    }
+
     let should_run = ask_for_confirmation(">> Run the generated program? [Y/n]", None);
 
     if should_run {
@@ -135,6 +239,7 @@ fn commit_workflow(mut chat_completions: completions::ChatCompletions) {
 
     let prompt = prompts::get_commit_user_prompt(commit_changes, &chat_completions.cli.hint);
     let mut commit_message = chat_completions.refine_loop(prompt, &mut spinner);
+    commit_message = lint_and_retry(&mut chat_completions, commit_message);
 
     if chat_completions.cli.gitmoji {
         commit_message = gitmoji::replace_gitmoji(commit_message);
@@ -151,12 +256,15 @@ fn commit_workflow(mut chat_completions: completions::ChatCompletions) {
         );
 
         if generate_commit_cmd {
-            let mut commit_cmd = "git commit -m '".to_string();
-            commit_cmd.push_str(commit_message.as_str());
-            commit_cmd.push_str("'");
+            let commit_cmd = format!("git commit -m {}", shell_single_quote(&commit_message));
 
             pprint(&commit_cmd, "bash");
 
+            if chat_completions.cli.dry_run {
+                explain_cmd(&commit_cmd);
+                return;
+            }
+
             let should_run = ask_for_confirmation(">> Run the generated commit? [Y/n]", None);
 
             if should_run {
@@ -174,19 +282,207 @@ fn commit_workflow(mut chat_completions: completions::ChatCompletions) {
     }
 }
 
-fn run_cmd(command: &str, shell: &str, spinner: &mut Spinner) -> (Vec<u8>, Vec<u8>) {
-    let output = Command::new(shell)
-        .arg("-c")
-        .arg(command)
-        .output()
-        .unwrap_or_else(|_| {
+fn undo_workflow(mut chat_completions: completions::ChatCompletions) {
+    chat_completions.set_system_prompt(prompts::SystemPrompt::Undo);
+    let mut spinner = Spinner::new(
+        Spinners::BouncingBar,
+        "Looking for a way to undo...".into(),
+    );
+
+    let status = git::get_status().unwrap_or_default();
+    let log = git::get_log(20).unwrap_or_else(|| {
+        spinner.stop_and_persist(
+            "✖".red().to_string().as_str(),
+            "Failed to read the repository history.".red().to_string(),
+        );
+        std::process::exit(1);
+    });
+
+    let prompt = prompts::get_undo_user_prompt(&status, &log);
+    let response = chat_completions.refine_loop(prompt, &mut spinner);
+    let (rationale, commands) = parse_undo_response(&response);
+
+    if let Some(rationale) = rationale {
+        println!("{}", rationale.italic());
+    }
+    pprint(&commands.join("\n"), "bash");
+
+    let should_run = ask_for_confirmation(
+        ">> Run the suggested undo sequence? [y/N]",
+        Some(Answer::NO),
+    );
+
+    if should_run {
+        for command in &commands {
+            spinner = Spinner::new(Spinners::BouncingBar, format!("Running: {command}"));
+            let (stdout, _) = run_cmd(command, &"bash", &mut spinner);
+
+            spinner.stop_and_persist(
+                "✔".green().to_string().as_str(),
+                "Command ran successfully".green().to_string(),
+            );
+
+            println!("{}", String::from_utf8_lossy(&stdout));
+        }
+    }
+}
+
+fn parse_undo_response(response: &str) -> (Option<String>, Vec<String>) {
+    let mut rationale = None;
+    let mut commands = Vec::new();
+
+    for line in response.lines() {
+        if let Some(value) = line.strip_prefix("Rationale:") {
+            rationale = Some(value.trim().to_string());
+        } else if let Some(rest) = line.strip_prefix("Command") {
+            let rest = rest.trim_start().trim_start_matches(|c: char| c.is_ascii_digit());
+            if let Some(value) = rest.strip_prefix(':') {
+                let command = value.trim().to_string();
+                if !command.is_empty() {
+                    commands.push(command);
+                }
+            }
+        }
+    }
+
+    if commands.is_empty() {
+        commands.push(response.trim().to_string());
+    }
+
+    return (rationale, commands);
+}
+
+fn release_workflow(mut chat_completions: completions::ChatCompletions) {
+    chat_completions.set_system_prompt(prompts::SystemPrompt::Release);
+    let mut spinner = Spinner::new(
+        Spinners::BouncingBar,
+        "Generating your release notes...".into(),
+    );
+
+    let from = chat_completions.cli.from.clone().unwrap_or_else(|| {
+        git::get_latest_tag().unwrap_or_else(|| {
             spinner.stop_and_persist(
                 "✖".red().to_string().as_str(),
-                "Failed to execute the generated program.".red().to_string(),
+                "Couldn't find a tag to start from, pass --from explicitly.".red().to_string(),
             );
             std::process::exit(1);
+        })
+    });
+    let to = chat_completions.cli.to.clone();
+
+    let log = git::get_log_range(&from, &to).unwrap_or_else(|| {
+        spinner.stop_and_persist(
+            "✖".red().to_string().as_str(),
+            format!("No commits found between {from} and {to}.").red().to_string(),
+        );
+        std::process::exit(1);
+    });
+
+    let prompt = prompts::get_release_user_prompt(&log);
+    let notes = chat_completions.refine_loop(prompt, &mut spinner);
+
+    pprint(&notes, "markdown");
+
+    let should_write =
+        ask_for_confirmation(">> Write these release notes to CHANGELOG.md? [Y/n]", None);
+
+    if should_write {
+        let existing = fs::read_to_string("CHANGELOG.md").unwrap_or_default();
+        let mut contents = notes.clone();
+        contents.push('\n');
+        contents.push_str(&existing);
+
+        fs::write("CHANGELOG.md", contents).unwrap_or_else(|_| {
+            println!("{}", "Failed to write CHANGELOG.md.".red());
+            std::process::exit(1);
         });
 
+        println!("{}", "CHANGELOG.md updated.".green());
+    }
+}
+
+const MAX_LINT_RETRIES: usize = 3;
+
+fn lint_and_retry(
+    chat_completions: &mut completions::ChatCompletions,
+    mut commit_message: String,
+) -> String {
+    let max_description_length = chat_completions.cli.max_description_length;
+    let mut violations = lint::lint_commit_message(&commit_message, max_description_length);
+
+    let mut attempt = 0;
+    while !violations.is_empty() && attempt < MAX_LINT_RETRIES {
+        attempt += 1;
+        let violation_text = violations
+            .iter()
+            .map(|v| v.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        let mut spinner = Spinner::new(
+            Spinners::BouncingBar,
+            format!("Regenerating commit message (attempt {attempt}/{MAX_LINT_RETRIES})..."),
+        );
+        let retry_prompt = prompts::get_commit_retry_prompt(&commit_message, &violation_text);
+        commit_message = chat_completions.refine_loop(retry_prompt, &mut spinner);
+        violations = lint::lint_commit_message(&commit_message, max_description_length);
+    }
+
+    if !violations.is_empty() {
+        println!(
+            "{}",
+            "The generated commit message still violates the conventional-commit rules:".red()
+        );
+        for violation in &violations {
+            println!("  - {}", violation.message);
+        }
+        if chat_completions.cli.strict {
+            std::process::exit(1);
+        }
+    }
+
+    return commit_message;
+}
+
+fn run_cmd(command: &str, shell: &str, spinner: &mut Spinner) -> (Vec<u8>, Vec<u8>) {
+    let result = if has_shell_metacharacters(command) {
+        spinner.stop_and_persist(
+            "⚠".yellow().to_string().as_str(),
+            "Shell metacharacters detected, running through the shell.".yellow().to_string(),
+        );
+        Command::new(shell).arg("-c").arg(command).output()
+    } else {
+        match shlex::split(command) {
+            // Unbalanced quotes etc. aren't a valid argv, but may still be valid
+            // shell syntax (e.g. an apostrophe inside a single-quoted string).
+            None => {
+                spinner.stop_and_persist(
+                    "⚠".yellow().to_string().as_str(),
+                    "Couldn't tokenize the command, running through the shell.".yellow().to_string(),
+                );
+                Command::new(shell).arg("-c").arg(command).output()
+            }
+            Some(argv) => match argv.split_first() {
+                Some((program, args)) => Command::new(program).args(args).output(),
+                None => {
+                    spinner.stop_and_persist(
+                        "✖".red().to_string().as_str(),
+                        "The generated command is empty.".red().to_string(),
+                    );
+                    std::process::exit(1);
+                }
+            },
+        }
+    };
+
+    let output = result.unwrap_or_else(|_| {
+        spinner.stop_and_persist(
+            "✖".red().to_string().as_str(),
+            "Failed to execute the generated program.".red().to_string(),
+        );
+        std::process::exit(1);
+    });
+
     if !output.status.success() {
         spinner.stop_and_persist(
             "✖".red().to_string().as_str(),
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 pub enum SystemPrompt {
     Cmd,
     Commit,
+    Undo,
+    Release,
 }
 
 impl SystemPrompt {
@@ -11,6 +13,8 @@ impl SystemPrompt {
         return match self {
             SystemPrompt::Cmd => cmd_system_prompt(),
             SystemPrompt::Commit => commit_system_prompt(options.gitmoji),
+            SystemPrompt::Undo => undo_system_prompt(),
+            SystemPrompt::Release => release_system_prompt(),
         };
     }
 }
@@ -63,6 +67,47 @@ pub fn commit_system_prompt(gitmoji: bool) -> Prompt {
     return Prompt::build("system".to_string(), content);
 }
 
+pub fn undo_system_prompt() -> Prompt {
+    let mut content = String::new();
+    content.push_str(
+        "You are an assistant to a programmer that needs to undo their most recent git operation.",
+    );
+    content.push_str(
+        "\nYour task is to read the repository status and recent history and propose the safest sequence of git commands to reverse the most recent operation (e.g. git reset --soft HEAD~1, git revert, git restore).",
+    );
+    content.push_str("\nFollowing the format:\nRationale: <one short sentence explaining why>\nCommand 1: <command>\nCommand 2: <command>\n...\n");
+    content.push_str("List every command needed, in the order they must run, one per `Command N:` line.\n");
+    return Prompt::build("system".to_string(), content);
+}
+
+pub fn get_undo_user_prompt(status: &str, log: &str) -> Prompt {
+    let mut content = String::new();
+    content.push_str("Here is the current repository status (git status --porcelain):\n");
+    content.push_str(status);
+    content.push_str("\nHere is the recent commit history (git log --oneline):\n");
+    content.push_str(log);
+    return Prompt::build("user".to_string(), content);
+}
+
+pub fn release_system_prompt() -> Prompt {
+    let mut content = String::new();
+    content.push_str(
+        "You are an assistant to a programmer that is preparing release notes for a new version.",
+    );
+    content.push_str(
+        "\nYour task is to cluster the given commits into sections and produce changelog entries, inferring the section from conventional-commit types when present (feat -> Features, fix -> Fixes, perf -> Performance, a \"!\" or \"BREAKING CHANGE\" -> Breaking Changes, anything else -> Other).",
+    );
+    content.push_str("\nReturn the result as Markdown with a heading per non-empty section and a bullet per commit.");
+    return Prompt::build("system".to_string(), content);
+}
+
+pub fn get_release_user_prompt(log: &str) -> Prompt {
+    let mut content = String::new();
+    content.push_str("Generate release notes for the following commits:\n");
+    content.push_str(log);
+    return Prompt::build("user".to_string(), content);
+}
+
 pub fn get_cmd_user_prompt(prompt: &str) -> Prompt {
     let os_hint = hint_os();
     return Prompt::build("user".to_string(), format!("{}{}:\n", prompt, os_hint));
@@ -82,6 +127,15 @@ pub fn get_commit_user_prompt(changes: Vec<String>, hint: &Option<String>) -> Pr
     return Prompt::build("user".to_string(), content);
 }
 
+pub fn get_commit_retry_prompt(previous_message: &str, violations: &str) -> Prompt {
+    let mut content = String::new();
+    content.push_str(format!("Your previous message violated: {}", violations).as_str());
+    content.push_str(" Please regenerate a commit message that fixes these issues while keeping the same intent.\n\n");
+    content.push_str("Previous message:\n");
+    content.push_str(previous_message);
+    return Prompt::build("user".to_string(), content);
+}
+
 fn hint_os() -> String {
     let os_hint = if cfg!(target_os = "macos") {
         " (on macOS)"